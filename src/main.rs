@@ -1,10 +1,12 @@
-use clap::Parser;
+use clap::{Parser, Subcommand, ValueEnum};
+use globset::{Glob, GlobSet, GlobSetBuilder};
 use std::fmt;
-use std::fs::File;
-use std::io::{self, Error};
-use std::path::{Path, PathBuf, StripPrefixError};
+use std::fs::{self, File};
+use std::io::{self, Error, Read, Write};
+use std::path::{Component, Path, PathBuf, StripPrefixError};
 use walkdir::WalkDir;
 use zip::write::SimpleFileOptions;
+use zip::ZipArchive;
 use zip::ZipWriter;
 
 #[derive(Parser)]
@@ -17,8 +19,27 @@ use zip::ZipWriter;
                   Just drag & drop to create ZIP files!"
 )]
 struct Args {
+    /// Subcommand to run; without one, the top-level arguments create an archive
+    #[command(subcommand)]
+    command: Option<Command>,
+
+    #[command(flatten)]
+    zip: ZipArgs,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Create a ZIP archive from one or more sources (the default action)
+    Zip(ZipArgs),
+
+    /// Extract a ZIP archive, guarding against Zip Slip
+    Extract(ExtractArgs),
+}
+
+#[derive(clap::Args)]
+struct ZipArgs {
     /// Directories to zip (supports drag and drop)
-    #[arg(value_parser, required = true)]
+    #[arg(value_parser)]
     sources: Vec<PathBuf>,
 
     /// Use verbose output
@@ -28,97 +49,551 @@ struct Args {
     /// Enable ZIP64 support for large files (>4GB)
     #[arg(long)]
     zip64: bool,
+
+    /// Compression method for every entry (defaults to a per-file heuristic)
+    #[arg(long, value_enum)]
+    method: Option<CompressionMethodArg>,
+
+    /// Compression level for the chosen method (method-specific range)
+    #[arg(long)]
+    level: Option<i64>,
+
+    /// Exclude paths matching this glob, e.g. "*.tmp" or "**/node_modules/**" (repeatable)
+    #[arg(long = "exclude")]
+    excludes: Vec<String>,
+
+    /// Only include paths matching this glob (repeatable)
+    #[arg(long = "include")]
+    includes: Vec<String>,
+
+    /// Skip dotfiles and dotdirectories
+    #[arg(long)]
+    no_hidden: bool,
+
+    /// AES-256 encrypt every entry with this password (WinZip-style, not legacy ZipCrypto)
+    #[arg(long)]
+    password: Option<String>,
+
+    /// Read the password from this file instead of the command line
+    #[arg(long)]
+    password_file: Option<PathBuf>,
+
+    /// Show a terminal progress bar while creating the archive
+    #[arg(long)]
+    progress: bool,
+}
+
+/// Environment variable checked for a password when neither `--password` nor
+/// `--password-file` is given, so the password need not appear in the process table.
+const PASSWORD_ENV_VAR: &str = "RIP_PASSWORD";
+
+/// Resolves a password from, in order: the `--password` flag, `--password-file`, then
+/// the `RIP_PASSWORD` environment variable.
+fn resolve_password(
+    password: &Option<String>,
+    password_file: &Option<PathBuf>,
+) -> Result<Option<String>, ZipError> {
+    if let Some(password) = password {
+        return Ok(Some(password.clone()));
+    }
+
+    if let Some(path) = password_file {
+        let contents = fs::read_to_string(path)?;
+        return Ok(Some(contents.trim_end_matches(['\n', '\r']).to_string()));
+    }
+
+    Ok(std::env::var(PASSWORD_ENV_VAR).ok())
+}
+
+/// Mirrors `zip::CompressionMethod`, restricted to the variants `rip` exposes on the CLI.
+#[derive(Clone, Copy, Debug, ValueEnum)]
+enum CompressionMethodArg {
+    Store,
+    Deflate,
+    Bzip2,
+    Zstd,
+}
+
+/// Extensions that are already compressed, so storing them uncompressed saves time for no loss.
+const INCOMPRESSIBLE_EXTENSIONS: &[&str] = &["jpg", "jpeg", "png", "zip", "mp4", "gif", "mp3"];
+
+/// Picks a compression method for `path`: the explicit `--method` if given, otherwise
+/// `Store` for already-compressed extensions and `Deflate` for everything else.
+fn resolve_compression_method(
+    explicit: Option<CompressionMethodArg>,
+    path: &Path,
+) -> Result<zip::CompressionMethod, ZipError> {
+    let method = explicit.unwrap_or_else(|| {
+        let is_incompressible = path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .map(|ext| INCOMPRESSIBLE_EXTENSIONS.contains(&ext.to_lowercase().as_str()))
+            .unwrap_or(false);
+
+        if is_incompressible {
+            CompressionMethodArg::Store
+        } else {
+            CompressionMethodArg::Deflate
+        }
+    });
+
+    match method {
+        CompressionMethodArg::Store => Ok(zip::CompressionMethod::Stored),
+        CompressionMethodArg::Deflate => Ok(zip::CompressionMethod::Deflated),
+        CompressionMethodArg::Bzip2 => {
+            #[cfg(feature = "bzip2")]
+            {
+                Ok(zip::CompressionMethod::Bzip2)
+            }
+            #[cfg(not(feature = "bzip2"))]
+            {
+                Err(ZipError::Io(Error::new(
+                    io::ErrorKind::InvalidInput,
+                    "rip was built without the \"bzip2\" feature",
+                )))
+            }
+        }
+        CompressionMethodArg::Zstd => {
+            #[cfg(feature = "zstd")]
+            {
+                Ok(zip::CompressionMethod::Zstd)
+            }
+            #[cfg(not(feature = "zstd"))]
+            {
+                Err(ZipError::Io(Error::new(
+                    io::ErrorKind::InvalidInput,
+                    "rip was built without the \"zstd\" feature",
+                )))
+            }
+        }
+    }
+}
+
+/// Applies WinZip-style AES-256 encryption to `file_options` when a password is given.
+fn apply_password<'p, T: zip::write::FileOptionExtension>(
+    file_options: zip::write::FileOptions<'static, T>,
+    password: Option<&'p str>,
+) -> Result<zip::write::FileOptions<'p, T>, ZipError> {
+    let Some(password) = password else {
+        return Ok(file_options);
+    };
+
+    #[cfg(feature = "aes-crypto")]
+    {
+        Ok(file_options.with_aes_encryption(zip::AesMode::Aes256, password))
+    }
+    #[cfg(not(feature = "aes-crypto"))]
+    {
+        let _ = password;
+        Err(ZipError::Io(Error::new(
+            io::ErrorKind::InvalidInput,
+            "rip was built without the \"aes-crypto\" feature",
+        )))
+    }
+}
+
+#[derive(clap::Args)]
+struct ExtractArgs {
+    /// ZIP archive to extract
+    archive: PathBuf,
+
+    /// Directory to extract into (defaults to the archive name without its extension)
+    #[arg(long)]
+    into: Option<PathBuf>,
+
+    /// Use verbose output
+    #[arg(short, long)]
+    verbose: bool,
+
+    /// Password to decrypt an AES-256 encrypted archive with
+    #[arg(long)]
+    password: Option<String>,
+
+    /// Read the password from this file instead of the command line
+    #[arg(long)]
+    password_file: Option<PathBuf>,
 }
 
 #[derive(Debug)]
 enum ZipError {
-    IoError(Error),
-    StripPrefixError(StripPrefixError),
-    ZipError(zip::result::ZipError),
+    Io(Error),
+    StripPrefix(StripPrefixError),
+    Zip(zip::result::ZipError),
+    Glob(globset::Error),
 }
 
 impl From<Error> for ZipError {
     fn from(err: Error) -> Self {
-        ZipError::IoError(err)
+        ZipError::Io(err)
     }
 }
 
 impl From<StripPrefixError> for ZipError {
     fn from(err: StripPrefixError) -> Self {
-        ZipError::StripPrefixError(err)
+        ZipError::StripPrefix(err)
     }
 }
 
 impl From<zip::result::ZipError> for ZipError {
     fn from(err: zip::result::ZipError) -> Self {
-        ZipError::ZipError(err)
+        ZipError::Zip(err)
+    }
+}
+
+impl From<globset::Error> for ZipError {
+    fn from(err: globset::Error) -> Self {
+        ZipError::Glob(err)
     }
 }
 
 impl fmt::Display for ZipError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
-            ZipError::IoError(err) => write!(f, "IO error: {}", err),
-            ZipError::StripPrefixError(err) => write!(f, "Path error: {}", err),
-            ZipError::ZipError(err) => write!(f, "ZIP error: {}", err),
+            ZipError::Io(err) => write!(f, "IO error: {}", err),
+            ZipError::StripPrefix(err) => write!(f, "Path error: {}", err),
+            ZipError::Zip(err) => write!(f, "ZIP error: {}", err),
+            ZipError::Glob(err) => write!(f, "Glob pattern error: {}", err),
+        }
+    }
+}
+
+/// Options controlling how `create_zip` builds an archive.
+#[derive(Default)]
+struct CreateZipOptions {
+    verbose: bool,
+    use_zip64: bool,
+    method: Option<CompressionMethodArg>,
+    level: Option<i64>,
+    include: Option<GlobSet>,
+    exclude: Option<GlobSet>,
+    no_hidden: bool,
+    password: Option<String>,
+}
+
+impl CreateZipOptions {
+    fn from_args(args: &ZipArgs) -> Result<Self, ZipError> {
+        Ok(Self {
+            verbose: args.verbose,
+            use_zip64: args.zip64,
+            method: args.method,
+            level: args.level,
+            include: build_globset(&args.includes)?,
+            exclude: build_globset(&args.excludes)?,
+            no_hidden: args.no_hidden,
+            password: resolve_password(&args.password, &args.password_file)?,
+        })
+    }
+
+    /// Whether `--exclude`/`--no-hidden` rule out `relative_path`/`name`. Checked by
+    /// `collect_zip_entries` as it walks, via `WalkDir`'s `filter_entry`, so an excluded
+    /// directory's contents are never descended into or `stat`'d in the first place.
+    fn excludes(&self, relative_path: &Path, name: &str) -> bool {
+        if self.no_hidden && is_hidden(relative_path) {
+            return true;
+        }
+
+        if let Some(exclude) = &self.exclude {
+            if exclude.is_match(name) {
+                return true;
+            }
+        }
+
+        false
+    }
+
+    /// Whether `relative_path` (its forward-slash form `name`) should be added to the archive.
+    fn allows(&self, relative_path: &Path, name: &str) -> bool {
+        if self.excludes(relative_path, name) {
+            return false;
+        }
+
+        if let Some(include) = &self.include {
+            if !include.is_match(name) {
+                return false;
+            }
+        }
+
+        true
+    }
+}
+
+fn build_globset(patterns: &[String]) -> Result<Option<GlobSet>, ZipError> {
+    if patterns.is_empty() {
+        return Ok(None);
+    }
+
+    let mut builder = GlobSetBuilder::new();
+    for pattern in patterns {
+        builder.add(Glob::new(pattern)?);
+    }
+
+    Ok(Some(builder.build()?))
+}
+
+/// A dotfile/dotdirectory anywhere in the path makes the whole entry hidden.
+fn is_hidden(relative_path: &Path) -> bool {
+    relative_path.components().any(|component| {
+        matches!(component, Component::Normal(s) if s.to_string_lossy().starts_with('.'))
+    })
+}
+
+/// A snapshot of `create_zip`'s progress, suitable for a terminal progress bar or any
+/// other UI a library caller wants to drive off of it.
+#[derive(Debug, Clone)]
+struct Progress {
+    files_done: u64,
+    files_total: u64,
+    bytes_done: u64,
+    bytes_total: u64,
+    current_path: PathBuf,
+}
+
+/// Running totals tracked while `create_zip` writes a tree, used to build [`Progress`] snapshots.
+#[derive(Default)]
+struct ProgressState {
+    files_done: u64,
+    files_total: u64,
+    bytes_done: u64,
+    bytes_total: u64,
+}
+
+impl ProgressState {
+    fn snapshot(&self, current_path: &Path) -> Progress {
+        Progress {
+            files_done: self.files_done,
+            files_total: self.files_total,
+            bytes_done: self.bytes_done,
+            bytes_total: self.bytes_total,
+            current_path: current_path.to_path_buf(),
+        }
+    }
+}
+
+/// A tree entry that survived `options`' filters, staged for writing into the archive.
+struct ZipEntry {
+    relative_path: PathBuf,
+    name: String,
+    path: PathBuf,
+    file_type: fs::FileType,
+    size: u64,
+}
+
+/// Whether `collect_zip_entries` should keep `entry` (relative to `source_dir`) and, for a
+/// directory, descend into it, per `options`' `--exclude`/`--no-hidden` rules; `false`
+/// stops the walk at `entry`. Used as a
+/// `WalkDir::filter_entry` predicate so an excluded directory (e.g. `.git/`,
+/// `node_modules/`) is pruned from the walk instead of being fully traversed and
+/// `stat`'d only to have every entry under it discarded afterwards.
+fn walk_allows(source_dir: &Path, options: &CreateZipOptions, entry: &walkdir::DirEntry) -> bool {
+    let path = entry.path();
+    let relative_path = match path.strip_prefix(source_dir) {
+        Ok(p) => p,
+        Err(_) => return true,
+    };
+
+    if relative_path.as_os_str().is_empty() {
+        return true;
+    }
+
+    let name = relative_path.to_string_lossy();
+    let match_name = if entry.file_type().is_dir() {
+        format!("{}/", name)
+    } else {
+        name.into_owned()
+    };
+
+    !options.excludes(relative_path, &match_name)
+}
+
+/// Walks `source_dir` once, applying `options`' filters, and returns every entry that
+/// belongs in the archive. Walking once lets `create_zip` size its progress totals
+/// up front instead of re-walking the tree before writing it.
+fn collect_zip_entries(source_dir: &Path, options: &CreateZipOptions) -> Result<Vec<ZipEntry>, ZipError> {
+    // シンボリックリンクは辿らず、エントリとして記録する（下記参照）
+    let walkdir = WalkDir::new(source_dir)
+        .follow_links(false)
+        .same_file_system(true)
+        .max_depth(100) // 深すぎる再帰を防ぐ
+        .into_iter()
+        .filter_entry(|entry| walk_allows(source_dir, options, entry));
+
+    let mut entries = Vec::new();
+
+    for entry in walkdir {
+        let entry = entry.map_err(Error::other)?;
+        let path = entry.path();
+        let relative_path = path.strip_prefix(source_dir)?.to_path_buf();
+
+        if relative_path.as_os_str().is_empty() {
+            continue;
         }
+
+        // 不正なパスがないかチェック
+        if relative_path
+            .components()
+            .any(|component| matches!(component, Component::ParentDir))
+        {
+            continue;
+        }
+
+        let name = match relative_path.to_str() {
+            Some(s) => s.to_string(),
+            None => relative_path.to_string_lossy().into_owned(),
+        };
+
+        let file_type = entry.file_type();
+
+        // `add_directory` always stores directories with a trailing slash (see `create_zip`),
+        // so match a directory's own entry against patterns the same way, or a pattern like
+        // "**/node_modules/**" would filter only the files inside it and leave an empty
+        // "node_modules/" entry behind.
+        let match_name = if file_type.is_dir() {
+            format!("{}/", name)
+        } else {
+            name.clone()
+        };
+
+        if !options.allows(&relative_path, &match_name) {
+            continue;
+        }
+
+        let size = if file_type.is_file() {
+            entry.metadata().map_err(Error::other)?.len()
+        } else {
+            0
+        };
+
+        entries.push(ZipEntry {
+            relative_path,
+            name,
+            path: path.to_path_buf(),
+            file_type,
+            size,
+        });
     }
+
+    Ok(entries)
+}
+
+/// Copies `reader` into `writer` in chunks, updating `state` and reporting a
+/// [`Progress`] snapshot after each chunk so a single multi-gigabyte file still
+/// produces incremental progress instead of one jump at the end.
+fn copy_with_progress(
+    reader: &mut impl Read,
+    writer: &mut impl Write,
+    state: &mut ProgressState,
+    current_path: &Path,
+    on_progress: &mut dyn FnMut(Progress),
+) -> Result<(), ZipError> {
+    let mut buffer = [0u8; 64 * 1024];
+    loop {
+        let read = reader.read(&mut buffer)?;
+        if read == 0 {
+            break;
+        }
+
+        writer.write_all(&buffer[..read])?;
+        state.bytes_done += read as u64;
+        on_progress(state.snapshot(current_path));
+    }
+
+    Ok(())
 }
 
-fn create_zip(source_dir: &Path, target_zip: &Path, verbose: bool, use_zip64: bool) -> Result<(), ZipError> {
+fn create_zip(
+    source_dir: &Path,
+    target_zip: &Path,
+    options: &CreateZipOptions,
+) -> Result<(), ZipError> {
+    create_zip_with_progress(source_dir, target_zip, options, &mut |_| {})
+}
+
+/// Like [`create_zip`], but invokes `on_progress` after every chunk of a regular file and
+/// once more when that file is done, so a caller can render a `--progress` bar or other UI.
+/// Directory and symlink entries do not report progress.
+fn create_zip_with_progress(
+    source_dir: &Path,
+    target_zip: &Path,
+    options: &CreateZipOptions,
+    on_progress: &mut dyn FnMut(Progress),
+) -> Result<(), ZipError> {
     if !source_dir.exists() {
-        return Err(ZipError::IoError(Error::new(
+        return Err(ZipError::Io(Error::new(
             io::ErrorKind::NotFound,
             format!("Source directory does not exist: {}", source_dir.display()),
         )));
     }
 
-    if verbose {
+    if options.verbose {
         println!("Creating ZIP file: {}", target_zip.display());
     }
 
+    let entries = collect_zip_entries(source_dir, options)?;
+
+    let mut state = ProgressState {
+        files_total: entries.iter().filter(|e| e.file_type.is_file()).count() as u64,
+        bytes_total: entries
+            .iter()
+            .filter(|e| e.file_type.is_file())
+            .map(|e| e.size)
+            .sum(),
+        ..ProgressState::default()
+    };
+
     let zip_file = File::create(target_zip)?;
     let mut zip = ZipWriter::new(zip_file);
 
-    let options = SimpleFileOptions::default()
-        .compression_method(zip::CompressionMethod::Deflated)
+    let base_options = SimpleFileOptions::default()
         .unix_permissions(0o755)
-        .large_file(use_zip64);
-
-    // シンボリックリンクは追跡せず、警告を表示
-    let walkdir = WalkDir::new(source_dir)
-        .follow_links(false)
-        .same_file_system(true)
-        .max_depth(100); // 深すぎる再帰を防ぐ
+        .large_file(options.use_zip64);
 
-    for entry in walkdir {
-        let entry = entry.map_err(|e| Error::new(io::ErrorKind::Other, e))?;
-        let path = entry.path();
+    for entry in &entries {
+        let name = &entry.name;
+        let path = entry.path.as_path();
 
-        if path.is_file() {
-            let relative_path = path.strip_prefix(source_dir)?;
+        if entry.file_type.is_symlink() {
+            let target = fs::read_link(path)?;
+            let target_name = match target.to_str() {
+                Some(s) => s.to_string(),
+                None => target.to_string_lossy().into_owned(),
+            };
 
-            // 不正なパスがないかチェック
-            if relative_path
-                .components()
-                .any(|component| matches!(component, std::path::Component::ParentDir))
-            {
-                continue;
+            if options.verbose {
+                println!("Adding symlink: {} -> {}", name, target_name);
             }
 
-            let name = match relative_path.to_str() {
-                Some(s) => s.to_string(),
-                None => relative_path.to_string_lossy().into_owned(),
-            };
+            let mode = symlink_permission_bits(path)?;
+            let symlink_options = base_options.unix_permissions(mode);
+            let symlink_options = apply_password(symlink_options, options.password.as_deref())?;
+            zip.add_symlink(name, &target_name, symlink_options)?;
+        } else if entry.file_type.is_dir() {
+            if options.verbose {
+                println!("Adding directory: {}", name);
+            }
 
-            if verbose {
+            zip.add_directory(name, base_options)?;
+        } else if entry.file_type.is_file() {
+            if options.verbose {
                 println!("Adding file: {}", name);
             }
 
-            zip.start_file(&name, options)?;
+            let method = resolve_compression_method(options.method, path)?;
+            let file_options = base_options.compression_method(method);
+            // `Stored` entries reject any compression_level at all, so only carry it
+            // through for methods that actually compress.
+            let file_options = if method == zip::CompressionMethod::Stored {
+                file_options
+            } else {
+                file_options.compression_level(options.level)
+            };
+            let file_options = apply_password(file_options, options.password.as_deref())?;
+            zip.start_file(name, file_options)?;
 
             let mut file = File::open(path)?;
-            io::copy(&mut file, &mut zip)?;
+            copy_with_progress(&mut file, &mut zip, &mut state, &entry.relative_path, on_progress)?;
+
+            state.files_done += 1;
+            on_progress(state.snapshot(&entry.relative_path));
         }
     }
 
@@ -126,6 +601,156 @@ fn create_zip(source_dir: &Path, target_zip: &Path, verbose: bool, use_zip64: bo
     Ok(())
 }
 
+/// Unix permission bits (masked to `0o777`) for a symlink, read from its own metadata
+/// rather than its target's. Non-Unix platforms have no such bits, so this falls back to `0o644`.
+fn symlink_permission_bits(path: &Path) -> Result<u32, ZipError> {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        Ok(path.symlink_metadata()?.permissions().mode() & 0o777)
+    }
+    #[cfg(not(unix))]
+    {
+        let _ = path;
+        Ok(0o644)
+    }
+}
+
+/// Rejects paths that could escape the extraction directory (Zip Slip): no `..`,
+/// no absolute components, no drive-letter/root prefixes.
+fn is_safe_relative_path(path: &Path) -> bool {
+    path.components()
+        .all(|component| matches!(component, Component::Normal(_)))
+}
+
+/// Defense in depth against Zip Slip via a symlink written earlier in the same
+/// extraction: `path` (already created on disk) must still resolve, symlinks and all,
+/// to somewhere under `canonical_target_dir`.
+fn ensure_within_target(canonical_target_dir: &Path, path: &Path) -> Result<(), ZipError> {
+    let canonical_path = path.canonicalize()?;
+    if !canonical_path.starts_with(canonical_target_dir) {
+        return Err(ZipError::Io(Error::new(
+            io::ErrorKind::InvalidData,
+            format!(
+                "Zip entry would extract outside the target directory: {}",
+                path.display()
+            ),
+        )));
+    }
+
+    Ok(())
+}
+
+fn extract_zip(
+    archive_path: &Path,
+    target_dir: &Path,
+    verbose: bool,
+    password: Option<&str>,
+) -> Result<(), ZipError> {
+    if !archive_path.exists() {
+        return Err(ZipError::Io(Error::new(
+            io::ErrorKind::NotFound,
+            format!("Archive does not exist: {}", archive_path.display()),
+        )));
+    }
+
+    if verbose {
+        println!("Extracting ZIP file: {}", archive_path.display());
+    }
+
+    let zip_file = File::open(archive_path)?;
+    let mut archive = ZipArchive::new(zip_file)?;
+
+    fs::create_dir_all(target_dir)?;
+    let canonical_target_dir = target_dir.canonicalize()?;
+
+    for i in 0..archive.len() {
+        let mut entry = match password {
+            Some(password) => archive.by_index_decrypt(i, password.as_bytes())?,
+            None => archive.by_index(i)?,
+        };
+
+        // バックスラッシュ区切りのエントリ名も正規化してから安全性をチェック
+        let name = entry.name().replace('\\', "/");
+        let relative_path = Path::new(&name);
+
+        if !is_safe_relative_path(relative_path) {
+            if verbose {
+                eprintln!("Skipping unsafe entry: {}", name);
+            }
+            continue;
+        }
+
+        let out_path = target_dir.join(relative_path);
+        let mode = entry.unix_mode();
+        let is_symlink_entry = mode.is_some_and(|mode| mode & 0o170000 == 0o120000);
+
+        if entry.is_dir() {
+            fs::create_dir_all(&out_path)?;
+            ensure_within_target(&canonical_target_dir, &out_path)?;
+        } else if is_symlink_entry {
+            let mut link_target = String::new();
+            entry.read_to_string(&mut link_target)?;
+
+            // An entry's symlink target is also attacker-controlled: an absolute path or
+            // one that climbs out with `..` would let a later entry (e.g. `link/pwned.txt`)
+            // write through it to anywhere on disk (Zip Slip via symlink).
+            if !is_safe_relative_path(Path::new(&link_target)) {
+                if verbose {
+                    eprintln!(
+                        "Skipping symlink with unsafe target: {} -> {}",
+                        name, link_target
+                    );
+                }
+                continue;
+            }
+
+            if let Some(parent) = out_path.parent() {
+                fs::create_dir_all(parent)?;
+                ensure_within_target(&canonical_target_dir, parent)?;
+            }
+
+            #[cfg(unix)]
+            std::os::unix::fs::symlink(&link_target, &out_path)?;
+            #[cfg(not(unix))]
+            fs::write(&out_path, &link_target)?;
+        } else {
+            if let Some(parent) = out_path.parent() {
+                fs::create_dir_all(parent)?;
+                ensure_within_target(&canonical_target_dir, parent)?;
+            }
+
+            let mut out_file = File::create(&out_path)?;
+            io::copy(&mut entry, &mut out_file)?;
+        }
+
+        #[cfg(unix)]
+        if !is_symlink_entry {
+            if let Some(mode) = mode {
+                use std::os::unix::fs::PermissionsExt;
+                fs::set_permissions(&out_path, fs::Permissions::from_mode(mode))?;
+            }
+        }
+
+        if verbose {
+            println!("Extracted: {}", out_path.display());
+        }
+    }
+
+    Ok(())
+}
+
+fn get_extract_dir(archive_path: &Path) -> PathBuf {
+    let stem = archive_path
+        .file_stem()
+        .unwrap_or_else(|| std::ffi::OsStr::new("archive"));
+
+    archive_path
+        .parent()
+        .unwrap_or_else(|| Path::new("."))
+        .join(stem)
+}
+
 fn sanitize_filename(name: &str) -> String {
     name.chars()
         .map(|c| match c {
@@ -175,14 +800,61 @@ fn pause() {
     let _ = std::io::stdin().read(&mut [0u8]).unwrap();
 }
 
-fn main() {
-    let args = Args::parse();
+/// Renders `progress` as a single updating terminal line for `--progress`.
+fn print_progress_bar(progress: Progress) {
+    let percent = if progress.bytes_total == 0 {
+        100.0
+    } else {
+        100.0 * progress.bytes_done as f64 / progress.bytes_total as f64
+    };
+
+    print!(
+        "\r[{:>3.0}%] {}/{} files, {}/{} bytes - {}",
+        percent,
+        progress.files_done,
+        progress.files_total,
+        progress.bytes_done,
+        progress.bytes_total,
+        progress.current_path.display()
+    );
+    let _ = io::stdout().flush();
+}
+
+fn run_zip(args: ZipArgs) {
+    if args.sources.is_empty() {
+        use clap::CommandFactory;
+        Args::command()
+            .error(
+                clap::error::ErrorKind::MissingRequiredArgument,
+                "at least one source is required",
+            )
+            .exit();
+    }
+
+    let show_progress = args.progress;
+
+    let options = match CreateZipOptions::from_args(&args) {
+        Ok(options) => options,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            std::process::exit(1);
+        }
+    };
 
-    for source in args.sources {
-        let zip_path = get_zip_path(&source);
+    for source in &args.sources {
+        let zip_path = get_zip_path(source);
 
-        match create_zip(&source, &zip_path, args.verbose, args.zip64) {
+        let result = if show_progress {
+            create_zip_with_progress(source, &zip_path, &options, &mut print_progress_bar)
+        } else {
+            create_zip(source, &zip_path, &options)
+        };
+
+        match result {
             Ok(_) => {
+                if show_progress {
+                    println!();
+                }
                 println!("Successfully created ZIP file: {}", zip_path.display());
             }
             Err(e) => {
@@ -190,6 +862,36 @@ fn main() {
             }
         }
     }
+}
+
+fn run_extract(args: ExtractArgs) {
+    let target_dir = args.into.unwrap_or_else(|| get_extract_dir(&args.archive));
+
+    let password = match resolve_password(&args.password, &args.password_file) {
+        Ok(password) => password,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            std::process::exit(1);
+        }
+    };
+
+    match extract_zip(&args.archive, &target_dir, args.verbose, password.as_deref()) {
+        Ok(_) => {
+            println!("Successfully extracted to: {}", target_dir.display());
+        }
+        Err(e) => {
+            eprintln!("Error extracting {}: {}", args.archive.display(), e);
+        }
+    }
+}
+
+fn main() {
+    let args = Args::parse();
+
+    match args.command.unwrap_or(Command::Zip(args.zip)) {
+        Command::Zip(zip_args) => run_zip(zip_args),
+        Command::Extract(extract_args) => run_extract(extract_args),
+    }
 
     // コマンドラインから実行された場合のみ終了を遅延させる
     #[cfg(windows)]
@@ -201,8 +903,9 @@ fn main() {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::collections::HashMap;
     use std::fs;
-    use std::io::{Cursor, Write};
+    use std::io::{Cursor, Read, Write};
     use tempfile::TempDir;
     use zip::ZipArchive;
 
@@ -219,7 +922,7 @@ mod tests {
 
         // ZIPファイルを作成
         let zip_path = temp_dir.path().join("test.zip");
-        create_zip(&test_dir, &zip_path, false, false)?;
+        create_zip(&test_dir, &zip_path, &CreateZipOptions::default())?;
 
         // ZIPファイルを検証
         assert!(zip_path.exists());
@@ -246,7 +949,7 @@ mod tests {
         fs::write(&test_file_path, "テストデータ")?;
 
         let zip_path = temp_dir.path().join("test.zip");
-        create_zip(&test_dir, &zip_path, false, false)?;
+        create_zip(&test_dir, &zip_path, &CreateZipOptions::default())?;
 
         let zip_file = File::open(&zip_path)?;
         let archive = ZipArchive::new(zip_file)?;
@@ -275,7 +978,7 @@ mod tests {
         fs::write(&test_file_path, "テストデータ")?;
 
         let zip_path = temp_dir.path().join("test.zip");
-        create_zip(&base_dir, &zip_path, false, false)?;
+        create_zip(&base_dir, &zip_path, &CreateZipOptions::default())?;
 
         // ZIPの内容を確認
         let zip_file = File::open(&zip_path)?;
@@ -310,7 +1013,7 @@ mod tests {
         fs::write(&subfile_path, "サブディレクトリのテストデータ")?;
 
         let zip_path = temp_dir.path().join("cross_platform_test.zip");
-        create_zip(&test_dir, &zip_path, false, false)?;
+        create_zip(&test_dir, &zip_path, &CreateZipOptions::default())?;
 
         // ZIPの内容を確認
         let zip_file = File::open(&zip_path)?;
@@ -359,7 +1062,14 @@ mod tests {
 
         // ZIPファイル作成
         let zip_path = temp_dir.path().join("platform_test.zip");
-        create_zip(&test_dir, &zip_path, true, false)?; // verboseをtrueに
+        create_zip(
+            &test_dir,
+            &zip_path,
+            &CreateZipOptions {
+                verbose: true,
+                ..Default::default()
+            },
+        )?; // verboseをtrueに
 
         // 検証
         let zip_file = File::open(&zip_path)?;
@@ -396,7 +1106,7 @@ mod tests {
         fs::write(current_dir.join("テスト.txt"), "深い階層のテスト")?;
 
         let zip_path = temp_dir.path().join("long_paths_test.zip");
-        create_zip(&test_dir, &zip_path, false, false)?;
+        create_zip(&test_dir, &zip_path, &CreateZipOptions::default())?;
 
         // 検証
         let zip_file = File::open(&zip_path)?;
@@ -428,7 +1138,14 @@ mod tests {
         fs::write(&unix_path, "macOSスタイル")?;
 
         let zip_path = temp_dir.path().join("cross_platform.zip");
-        create_zip(&test_dir, &zip_path, true, false)?;
+        create_zip(
+            &test_dir,
+            &zip_path,
+            &CreateZipOptions {
+                verbose: true,
+                ..Default::default()
+            },
+        )?;
 
         // 検証
         let zip_file = File::open(&zip_path)?;
@@ -473,14 +1190,438 @@ mod tests {
         fs::create_dir(&test_dir).unwrap();
         fs::write(test_dir.join("test.txt"), b"test").unwrap();
 
-        let args = Args {
+        let args = ZipArgs {
             sources: vec![test_dir.clone()],
             verbose: false,
             zip64: true,
+            method: None,
+            level: None,
+            excludes: Vec::new(),
+            includes: Vec::new(),
+            no_hidden: false,
+            password: None,
+            password_file: None,
+            progress: false,
         };
 
         // CLIオプションが正しく処理されることを確認
         let zip_path = get_zip_path(&test_dir);
-        assert!(create_zip(&test_dir, &zip_path, args.verbose, args.zip64).is_ok());
+        let options = CreateZipOptions::from_args(&args).unwrap();
+        assert!(create_zip(&test_dir, &zip_path, &options).is_ok());
+    }
+
+    #[test]
+    fn test_compression_method_heuristic() -> Result<(), ZipError> {
+        let temp_dir = TempDir::new()?;
+        let test_dir = temp_dir.path().join("heuristic");
+        fs::create_dir(&test_dir)?;
+        fs::write(test_dir.join("notes.txt"), "compressible")?;
+        fs::write(test_dir.join("photo.jpg"), "already compressed")?;
+
+        let zip_path = temp_dir.path().join("heuristic.zip");
+        create_zip(&test_dir, &zip_path, &CreateZipOptions::default())?;
+
+        let zip_file = File::open(&zip_path)?;
+        let mut archive = ZipArchive::new(zip_file)?;
+
+        assert_eq!(
+            archive.by_name("notes.txt")?.compression(),
+            zip::CompressionMethod::Deflated
+        );
+        assert_eq!(
+            archive.by_name("photo.jpg")?.compression(),
+            zip::CompressionMethod::Stored
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_compression_method_explicit_override() -> Result<(), ZipError> {
+        let temp_dir = TempDir::new()?;
+        let test_dir = temp_dir.path().join("override");
+        fs::create_dir(&test_dir)?;
+        fs::write(test_dir.join("photo.jpg"), "already compressed")?;
+
+        let zip_path = temp_dir.path().join("override.zip");
+        create_zip(
+            &test_dir,
+            &zip_path,
+            &CreateZipOptions {
+                method: Some(CompressionMethodArg::Deflate),
+                ..Default::default()
+            },
+        )?;
+
+        let zip_file = File::open(&zip_path)?;
+        let mut archive = ZipArchive::new(zip_file)?;
+
+        assert_eq!(
+            archive.by_name("photo.jpg")?.compression(),
+            zip::CompressionMethod::Deflated
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_level_with_heuristic_store_does_not_error() -> Result<(), ZipError> {
+        let temp_dir = TempDir::new()?;
+        let test_dir = temp_dir.path().join("level-and-store");
+        fs::create_dir(&test_dir)?;
+        fs::write(test_dir.join("notes.txt"), "compressible")?;
+        fs::write(test_dir.join("photo.jpg"), "already compressed")?;
+
+        let zip_path = temp_dir.path().join("level-and-store.zip");
+        create_zip(
+            &test_dir,
+            &zip_path,
+            &CreateZipOptions {
+                level: Some(5),
+                ..Default::default()
+            },
+        )?;
+
+        let zip_file = File::open(&zip_path)?;
+        let mut archive = ZipArchive::new(zip_file)?;
+
+        assert_eq!(
+            archive.by_name("notes.txt")?.compression(),
+            zip::CompressionMethod::Deflated
+        );
+        assert_eq!(
+            archive.by_name("photo.jpg")?.compression(),
+            zip::CompressionMethod::Stored
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_exclude_and_no_hidden_filters() -> Result<(), ZipError> {
+        let temp_dir = TempDir::new()?;
+        let test_dir = temp_dir.path().join("filters");
+        fs::create_dir(&test_dir)?;
+        fs::write(test_dir.join("keep.rs"), "fn main() {}")?;
+        fs::write(test_dir.join("scratch.tmp"), "junk")?;
+        fs::write(test_dir.join(".hidden"), "secret")?;
+        fs::create_dir(test_dir.join("node_modules"))?;
+        fs::write(test_dir.join("node_modules").join("dep.js"), "dep")?;
+
+        let zip_path = temp_dir.path().join("filters.zip");
+        create_zip(
+            &test_dir,
+            &zip_path,
+            &CreateZipOptions {
+                exclude: build_globset(&["*.tmp".to_string(), "**/node_modules/**".to_string()])?,
+                no_hidden: true,
+                ..Default::default()
+            },
+        )?;
+
+        let zip_file = File::open(&zip_path)?;
+        let archive = ZipArchive::new(zip_file)?;
+        let file_names: Vec<String> = archive.file_names().map(|s| s.to_string()).collect();
+
+        assert!(file_names.contains(&"keep.rs".to_string()));
+        assert!(!file_names.iter().any(|name| name.ends_with(".tmp")));
+        assert!(!file_names.iter().any(|name| name.contains(".hidden")));
+        assert!(!file_names.iter().any(|name| name.ends_with("dep.js")));
+        assert!(!file_names.iter().any(|name| name.trim_end_matches('/') == "node_modules"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_walk_allows_prunes_excluded_directories() -> Result<(), ZipError> {
+        // `collect_zip_entries` passes `walk_allows` to `WalkDir::filter_entry`, which prunes
+        // a directory from the walk entirely when the predicate returns false for it (rather
+        // than just dropping it from the final entry list after a full traversal). So it's
+        // `walk_allows` itself, evaluated on the directory entry, that must say no.
+        let temp_dir = TempDir::new()?;
+        let test_dir = temp_dir.path().join("pruned");
+        fs::create_dir(&test_dir)?;
+        fs::write(test_dir.join("keep.rs"), "fn main() {}")?;
+        fs::create_dir(test_dir.join("node_modules"))?;
+        fs::write(test_dir.join("node_modules").join("dep.js"), "dep")?;
+
+        let options = CreateZipOptions {
+            exclude: build_globset(&["**/node_modules/**".to_string()])?,
+            ..Default::default()
+        };
+
+        let mut allowed = HashMap::new();
+        for entry in WalkDir::new(&test_dir).into_iter() {
+            let entry = entry.map_err(Error::other)?;
+            let relative_path = entry.path().strip_prefix(&test_dir)?;
+            if relative_path.as_os_str().is_empty() {
+                continue;
+            }
+            allowed.insert(
+                relative_path.to_string_lossy().into_owned(),
+                walk_allows(&test_dir, &options, &entry),
+            );
+        }
+
+        assert_eq!(allowed.get("keep.rs"), Some(&true));
+        assert_eq!(allowed.get("node_modules"), Some(&false));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_empty_directory_round_trip() -> Result<(), ZipError> {
+        let temp_dir = TempDir::new()?;
+        let source_dir = temp_dir.path().join("テスト");
+        fs::create_dir(&source_dir)?;
+        fs::create_dir(source_dir.join("空のフォルダー"))?;
+        fs::write(source_dir.join("日本語.txt"), "テストデータ")?;
+
+        let zip_path = temp_dir.path().join("empty_dir.zip");
+        create_zip(&source_dir, &zip_path, &CreateZipOptions::default())?;
+
+        let zip_file = File::open(&zip_path)?;
+        let archive = ZipArchive::new(zip_file)?;
+        let file_names: Vec<String> = archive.file_names().map(|s| s.to_string()).collect();
+
+        assert!(file_names
+            .iter()
+            .any(|name| name.trim_end_matches('/') == "空のフォルダー"));
+
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_symlink_round_trip() -> Result<(), ZipError> {
+        use std::os::unix::fs::symlink;
+
+        let temp_dir = TempDir::new()?;
+        let source_dir = temp_dir.path().join("symlinks");
+        fs::create_dir(&source_dir)?;
+        fs::write(source_dir.join("target.txt"), "link target")?;
+        symlink("target.txt", source_dir.join("link.txt"))?;
+
+        let zip_path = temp_dir.path().join("symlink.zip");
+        create_zip(&source_dir, &zip_path, &CreateZipOptions::default())?;
+
+        let zip_file = File::open(&zip_path)?;
+        let mut archive = ZipArchive::new(zip_file)?;
+
+        let mut link_entry = archive.by_name("link.txt")?;
+        assert_eq!(link_entry.unix_mode().unwrap() & 0o170000, 0o120000);
+
+        let mut link_target = String::new();
+        link_entry.read_to_string(&mut link_target)?;
+        assert_eq!(link_target, "target.txt");
+
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_symlink_extract_round_trip() -> Result<(), ZipError> {
+        use std::os::unix::fs::symlink;
+
+        let temp_dir = TempDir::new()?;
+        let source_dir = temp_dir.path().join("symlinks");
+        fs::create_dir(&source_dir)?;
+        fs::write(source_dir.join("target.txt"), "link target")?;
+        symlink("target.txt", source_dir.join("link.txt"))?;
+
+        let zip_path = temp_dir.path().join("symlink.zip");
+        create_zip(&source_dir, &zip_path, &CreateZipOptions::default())?;
+
+        let extract_dir = temp_dir.path().join("extracted");
+        extract_zip(&zip_path, &extract_dir, false, None)?;
+
+        let extracted_link = extract_dir.join("link.txt");
+        assert!(extracted_link.symlink_metadata()?.file_type().is_symlink());
+        assert_eq!(fs::read_link(&extracted_link)?, Path::new("target.txt"));
+        assert_eq!(fs::read_to_string(&extracted_link)?, "link target");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_extract_round_trip() -> Result<(), ZipError> {
+        let temp_dir = TempDir::new()?;
+        let source_dir = temp_dir.path().join("テスト");
+        fs::create_dir(&source_dir)?;
+        fs::write(source_dir.join("日本語.txt"), "テストデータ")?;
+        fs::create_dir_all(source_dir.join("フォルダー1").join("フォルダー2"))?;
+        fs::write(
+            source_dir.join("フォルダー1").join("フォルダー2").join("ネスト.txt"),
+            "ネストデータ",
+        )?;
+
+        let zip_path = temp_dir.path().join("test.zip");
+        create_zip(&source_dir, &zip_path, &CreateZipOptions::default())?;
+
+        let extract_dir = temp_dir.path().join("extracted");
+        extract_zip(&zip_path, &extract_dir, false, None)?;
+
+        assert_eq!(
+            fs::read_to_string(extract_dir.join("日本語.txt"))?,
+            "テストデータ"
+        );
+        assert_eq!(
+            fs::read_to_string(
+                extract_dir
+                    .join("フォルダー1")
+                    .join("フォルダー2")
+                    .join("ネスト.txt")
+            )?,
+            "ネストデータ"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_extract_rejects_zip_slip() -> Result<(), ZipError> {
+        let temp_dir = TempDir::new()?;
+        let zip_path = temp_dir.path().join("malicious.zip");
+
+        {
+            let zip_file = File::create(&zip_path)?;
+            let mut zip = ZipWriter::new(zip_file);
+            let options = SimpleFileOptions::default();
+            zip.start_file("../escaped.txt", options)?;
+            zip.write_all(b"should not escape")?;
+            zip.finish()?;
+        }
+
+        let extract_dir = temp_dir.path().join("extracted");
+        extract_zip(&zip_path, &extract_dir, false, None)?;
+
+        assert!(!temp_dir.path().join("escaped.txt").exists());
+        assert!(!extract_dir.join("../escaped.txt").exists());
+
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_extract_rejects_zip_slip_via_symlink() -> Result<(), ZipError> {
+        let temp_dir = TempDir::new()?;
+        let outside_dir = temp_dir.path().join("outside");
+        fs::create_dir(&outside_dir)?;
+        let zip_path = temp_dir.path().join("malicious.zip");
+
+        {
+            let zip_file = File::create(&zip_path)?;
+            let mut zip = ZipWriter::new(zip_file);
+            let options = SimpleFileOptions::default();
+
+            // A symlink entry pointing at an absolute path outside the extraction
+            // directory, followed by a regular file entry nested under it.
+            zip.add_symlink("link", outside_dir.to_str().unwrap(), options)?;
+            zip.start_file("link/pwned.txt", options)?;
+            zip.write_all(b"should not escape")?;
+            zip.finish()?;
+        }
+
+        let extract_dir = temp_dir.path().join("extracted");
+        extract_zip(&zip_path, &extract_dir, false, None)?;
+
+        assert!(!outside_dir.join("pwned.txt").exists());
+        assert!(!extract_dir.join("link").symlink_metadata()?.is_symlink());
+        assert_eq!(
+            fs::read_to_string(extract_dir.join("link").join("pwned.txt"))?,
+            "should not escape"
+        );
+
+        Ok(())
+    }
+
+    #[cfg(feature = "aes-crypto")]
+    #[test]
+    fn test_aes_encrypted_round_trip_requires_correct_password() -> Result<(), ZipError> {
+        let temp_dir = TempDir::new()?;
+        let source_dir = temp_dir.path().join("secret");
+        fs::create_dir_all(&source_dir)?;
+        fs::write(source_dir.join("plans.txt"), "top secret plans")?;
+
+        let zip_path = temp_dir.path().join("secret.zip");
+        let options = CreateZipOptions {
+            password: Some("hunter2".to_string()),
+            ..Default::default()
+        };
+        create_zip(&source_dir, &zip_path, &options)?;
+
+        let extract_dir = temp_dir.path().join("extracted");
+        extract_zip(&zip_path, &extract_dir, false, Some("hunter2"))?;
+        assert_eq!(
+            fs::read_to_string(extract_dir.join("plans.txt"))?,
+            "top secret plans"
+        );
+
+        let wrong_password_dir = temp_dir.path().join("extracted-wrong");
+        assert!(extract_zip(&zip_path, &wrong_password_dir, false, Some("incorrect")).is_err());
+
+        let no_password_dir = temp_dir.path().join("extracted-none");
+        assert!(extract_zip(&zip_path, &no_password_dir, false, None).is_err());
+
+        Ok(())
+    }
+
+    #[cfg(feature = "aes-crypto")]
+    #[test]
+    #[cfg(unix)]
+    fn test_aes_encrypts_symlink_targets() -> Result<(), ZipError> {
+        use std::os::unix::fs::symlink;
+
+        let temp_dir = TempDir::new()?;
+        let source_dir = temp_dir.path().join("secret-links");
+        fs::create_dir_all(&source_dir)?;
+        symlink("top/secret/target.txt", source_dir.join("link.txt"))?;
+
+        let zip_path = temp_dir.path().join("secret-links.zip");
+        let options = CreateZipOptions {
+            password: Some("hunter2".to_string()),
+            ..Default::default()
+        };
+        create_zip(&source_dir, &zip_path, &options)?;
+
+        let zip_file = File::open(&zip_path)?;
+        let mut archive = ZipArchive::new(zip_file)?;
+
+        assert!(archive.by_index_decrypt(0, b"incorrect").is_err());
+
+        let mut link_entry = archive.by_index_decrypt(0, b"hunter2")?;
+        let mut link_target = String::new();
+        link_entry.read_to_string(&mut link_target)?;
+        assert_eq!(link_target, "top/secret/target.txt");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_create_zip_reports_progress() -> Result<(), ZipError> {
+        let temp_dir = TempDir::new()?;
+        let source_dir = temp_dir.path().join("progress");
+        fs::create_dir_all(&source_dir)?;
+        fs::write(source_dir.join("a.txt"), "a".repeat(1000))?;
+        fs::write(source_dir.join("b.txt"), "b".repeat(2000))?;
+
+        let zip_path = temp_dir.path().join("progress.zip");
+        let mut snapshots = Vec::new();
+        create_zip_with_progress(
+            &source_dir,
+            &zip_path,
+            &CreateZipOptions::default(),
+            &mut |progress| snapshots.push(progress),
+        )?;
+
+        assert!(!snapshots.is_empty());
+        assert!(snapshots.iter().all(|p| p.files_total == 2));
+        assert!(snapshots.iter().all(|p| p.bytes_total == 3000));
+
+        let last = snapshots.last().unwrap();
+        assert_eq!(last.files_done, 2);
+        assert_eq!(last.bytes_done, 3000);
+
+        Ok(())
     }
 }